@@ -1,122 +1,298 @@
-use std::io::Write;
+use std::{
+    io::{Read, Write},
+    ops::Range,
+    path::{Path, PathBuf},
+};
 
 use anyhow::{bail, Context};
 use bstr::ByteSlice;
 use bytes::Bytes;
-use flate2::write::GzEncoder;
+use flate2::{read::GzDecoder, write::GzEncoder};
 use futures_util::TryStreamExt;
 use http::{
-    header::{ACCEPT_ENCODING, CONTENT_ENCODING, CONTENT_LENGTH, CONTENT_TYPE, USER_AGENT},
-    HeaderValue, Method, Response, StatusCode, Version,
+    header::{
+        ACCEPT_ENCODING, ACCEPT_RANGES, ALLOW, CONNECTION, CONTENT_ENCODING, CONTENT_LENGTH,
+        CONTENT_RANGE, CONTENT_TYPE, RANGE, SEC_WEBSOCKET_ACCEPT, SEC_WEBSOCKET_KEY, UPGRADE,
+        USER_AGENT,
+    },
+    response, HeaderMap, HeaderName, HeaderValue, Method, Response, StatusCode, Version,
 };
 use http_body::Frame;
-use http_body_util::{combinators::BoxBody as _BB, BodyExt, StreamBody};
-use tokio::io::{AsyncRead, AsyncWriteExt};
+use http_body_util::{BodyExt, StreamBody};
+use once_cell::sync::Lazy;
+use tokio::io::{AsyncRead, AsyncReadExt, AsyncSeekExt, AsyncWriteExt};
 use tokio_util::io::ReaderStream;
 
-use crate::{Request, ARGUMENTS};
+use crate::{
+    request::BoxBody,
+    router::{Params, Router},
+    ws, Request, ARGUMENTS,
+};
 
 const TEXT_PLAIN: &str = "text/plain";
 const OCTET_STREAM: &str = "application/octet-stream";
 const GZIP: &str = "gzip";
 
-type BoxBody = _BB<Bytes, std::io::Error>;
+static ROUTER: Lazy<Router> = Lazy::new(build_router);
+
+fn build_router() -> Router {
+    let mut router = Router::new();
+    router.route(Method::GET, "/", root);
+    router.route(Method::GET, "/echo/:msg", echo);
+    router.route(Method::GET, "/user-agent", user_agent);
+    router.route(Method::GET, "/files/:name", files_get);
+    router.route(Method::POST, "/files/:name", files_post);
+    router
+}
+
+/// What the caller should do with the connection after [`handle_request`] returns.
+pub enum Outcome {
+    /// A request/response was handled normally; the bool says whether to read another request
+    /// off the same connection.
+    Response(bool),
+    /// The request was a WebSocket handshake and a `101` has already been sent on `responder`.
+    /// The caller should stop speaking HTTP and hand the raw connection to [`ws::serve`].
+    Upgraded,
+}
 
-pub async fn handle_request<W>(request: Request<BoxBody>, responder: &mut W) -> anyhow::Result<()>
+/// Handles a single request on `responder`, reporting what the caller should do next: keep
+/// reading requests, close the connection, or hand it off as an upgraded WebSocket.
+pub async fn handle_request<W>(request: Request<BoxBody>, responder: &mut W) -> anyhow::Result<Outcome>
 where
     W: AsyncWriteExt + Unpin,
 {
-    let mut path_parts = request
-        .uri()
-        .path()
-        .split('/')
-        .skip(1)
-        .filter(|x| !x.is_empty());
-
-    let accepts = request.headers().get_all(ACCEPT_ENCODING);
-
-    let response = match path_parts.next().unwrap_or("") {
-        "" => Response::new(BodyType::Empty),
-        "echo" => {
-            let arg = path_parts.next().context("Missing arg")?;
-
-            let mut builder =
-                Response::builder().header(CONTENT_TYPE, HeaderValue::from_static(TEXT_PLAIN));
-
-            let content = if accepts
-                .into_iter()
-                .flat_map(|hv| hv.as_bytes().split_str(b", "))
-                .any(|hv| hv.eq_ignore_ascii_case(b"gzip"))
-            {
-                builder = builder.header(CONTENT_ENCODING, HeaderValue::from_static(GZIP));
-                encode_sync(arg)?
-            } else {
-                arg.as_bytes().to_owned()
-            };
-            builder
+    if let Some(client_key) = websocket_key(&request) {
+        send_response(ws_handshake(&client_key), true, responder).await?;
+        return Ok(Outcome::Upgraded);
+    }
+
+    let keep_alive = wants_keep_alive(&request);
+    let response = ROUTER.dispatch(request).await?;
+    send_response(response, keep_alive, responder).await?;
+    Ok(Outcome::Response(keep_alive))
+}
+
+/// Returns the `Sec-WebSocket-Key` if `request` is a WebSocket upgrade handshake per RFC 6455
+/// section 4.2.1: a `GET` request with an `Upgrade: websocket` header, a `Connection` header
+/// containing `Upgrade`, and the key itself.
+fn websocket_key<T>(request: &Request<T>) -> Option<String> {
+    if *request.method() != Method::GET {
+        return None;
+    }
+
+    let has_token = |name: &HeaderName, token: &str| {
+        request
+            .headers()
+            .get_all(name)
+            .into_iter()
+            .flat_map(|hv| hv.as_bytes().split_str(b","))
+            .any(|part| part.trim().eq_ignore_ascii_case(token.as_bytes()))
+    };
+
+    if !has_token(&UPGRADE, "websocket") || !has_token(&CONNECTION, "upgrade") {
+        return None;
+    }
+
+    request
+        .headers()
+        .get(SEC_WEBSOCKET_KEY)
+        .and_then(|value| value.to_str().ok())
+        .map(str::to_owned)
+}
+
+/// Builds the `101 Switching Protocols` response that completes a WebSocket handshake.
+fn ws_handshake(client_key: &str) -> Response<BodyType> {
+    Response::builder()
+        .status(StatusCode::SWITCHING_PROTOCOLS)
+        .header(UPGRADE, HeaderValue::from_static("websocket"))
+        .header(CONNECTION, HeaderValue::from_static("Upgrade"))
+        .header(SEC_WEBSOCKET_ACCEPT, ws::accept_key(client_key))
+        .body(BodyType::Empty)
+        .unwrap()
+}
+
+async fn root(_request: Request<BoxBody>, _params: Params) -> anyhow::Result<Response<BodyType>> {
+    Ok(Response::new(BodyType::Empty))
+}
+
+async fn echo(request: Request<BoxBody>, params: Params) -> anyhow::Result<Response<BodyType>> {
+    let arg = params.get("msg").context("Missing msg param")?;
+
+    let mut builder =
+        Response::builder().header(CONTENT_TYPE, HeaderValue::from_static(TEXT_PLAIN));
+
+    let content = if accepts_gzip(request.headers()) {
+        builder = builder.header(CONTENT_ENCODING, HeaderValue::from_static(GZIP));
+        encode_sync(arg)?
+    } else {
+        arg.as_bytes().to_owned()
+    };
+    Ok(builder
+        .header(CONTENT_LENGTH, content.len())
+        .body(BodyType::full(content))?)
+}
+
+async fn user_agent(
+    request: Request<BoxBody>,
+    _params: Params,
+) -> anyhow::Result<Response<BodyType>> {
+    Ok(request
+        .headers()
+        .get(USER_AGENT)
+        .map(|content| {
+            Response::builder()
+                .header(CONTENT_TYPE, HeaderValue::from_static(TEXT_PLAIN))
                 .header(CONTENT_LENGTH, content.len())
-                .body(BodyType::full(content))?
-        }
-        "user-agent" => request
+                .body(BodyType::full(content.as_bytes().to_owned()))
+        })
+        .transpose()?
+        .unwrap_or(not_found()))
+}
+
+async fn files_get(
+    request: Request<BoxBody>,
+    params: Params,
+) -> anyhow::Result<Response<BodyType>> {
+    let file_name = params.get("name").context("Missing name param")?;
+    let directory = ARGUMENTS
+        .directory
+        .as_ref()
+        .context("/files/ in path but no directory in arguments")?;
+    let file_path = directory.join(file_name);
+
+    let builder = Response::builder();
+    let content_type = content_type_for(file_name, request.uri().query());
+    let gz_path = gz_sidecar_path(&file_path);
+
+    // Open the file directly rather than checking existence first: a check-then-open risks the
+    // file disappearing in between, which would otherwise surface as an unhandled I/O error
+    // instead of a clean 404.
+    let gz_file = if accepts_gzip(request.headers()) {
+        tokio::fs::File::open(&gz_path).await.ok()
+    } else {
+        None
+    };
+
+    let response = if let Some(mut file) = gz_file {
+        let total = file.metadata().await?.len();
+        let Some(range) = resolve_range(request.headers(), total) else {
+            return Ok(range_not_satisfiable(total));
+        };
+
+        let builder = builder
+            .header(CONTENT_TYPE, HeaderValue::from_static(content_type))
+            .header(CONTENT_ENCODING, HeaderValue::from_static(GZIP));
+        let (builder, start, len) = apply_range_headers(builder, range, total);
+
+        file.seek(std::io::SeekFrom::Start(start)).await?;
+        builder.body(BodyType::chunked(file.take(len)))?
+    } else if let Ok(mut file) = tokio::fs::File::open(&file_path).await {
+        let total = file.metadata().await?.len();
+        let Some(range) = resolve_range(request.headers(), total) else {
+            return Ok(range_not_satisfiable(total));
+        };
+
+        let builder = builder.header(CONTENT_TYPE, HeaderValue::from_static(content_type));
+        let (builder, start, len) = apply_range_headers(builder, range, total);
+
+        file.seek(std::io::SeekFrom::Start(start)).await?;
+        builder.body(BodyType::chunked(file.take(len)))?
+    } else if let Ok(compressed) = tokio::fs::read(&gz_path).await {
+        // Only the compressed sidecar exists and the client didn't ask for gzip, so
+        // decompress it ourselves instead of 404ing.
+        let decoded = decode_sync(compressed)?;
+        let total = decoded.len() as u64;
+        let Some(range) = resolve_range(request.headers(), total) else {
+            return Ok(range_not_satisfiable(total));
+        };
+
+        let builder = builder.header(CONTENT_TYPE, HeaderValue::from_static(content_type));
+        let (builder, start, len) = apply_range_headers(builder, range, total);
+
+        let start = start as usize;
+        let end = start + len as usize;
+        builder.body(BodyType::full(decoded[start..end].to_vec()))?
+    } else {
+        not_found()
+    };
+    Ok(response)
+}
+
+async fn files_post(
+    request: Request<BoxBody>,
+    params: Params,
+) -> anyhow::Result<Response<BodyType>> {
+    let file_name = params.get("name").context("Missing name param")?;
+    let directory = ARGUMENTS
+        .directory
+        .as_ref()
+        .context("/files/ in path but no directory in arguments")?;
+    let file_path = directory.join(file_name);
+
+    let Some(body) = request.body else {
+        bail!("No body");
+    };
+    tokio::fs::create_dir_all(&directory).await?;
+    let mut file = tokio::fs::OpenOptions::new()
+        .write(true)
+        .truncate(true)
+        .create(true)
+        .open(file_path)
+        .await?;
+
+    write_body_to(&mut file, BodyType::Chunked(body)).await?;
+    Ok(Response::builder()
+        .status(StatusCode::CREATED)
+        .body(BodyType::Empty)?)
+}
+
+/// Whether the connection carrying `request` should be kept open afterwards, per RFC 7230
+/// section 6.3: HTTP/1.1 defaults to keep-alive, earlier versions default to close, and an
+/// explicit `Connection` header always wins.
+fn wants_keep_alive<T>(request: &Request<T>) -> bool {
+    let has_token = |token: &str| {
+        request
             .headers()
-            .get(USER_AGENT)
-            .map(|content| {
-                Response::builder()
-                    .header(CONTENT_TYPE, HeaderValue::from_static(TEXT_PLAIN))
-                    .header(CONTENT_LENGTH, content.len())
-                    .body(BodyType::full(content.as_bytes().to_owned()))
-            })
-            .transpose()?
-            .unwrap_or(not_found()),
-        "files" => 'files: {
-            let file_name = path_parts.next().context("Missing file name")?;
-            let directory = ARGUMENTS
-                .directory
-                .as_ref()
-                .context("/files/ in path but no directory in arguments")?;
-            let file_path = directory.join(file_name);
-
-            let builder = Response::builder();
-            match *request.method() {
-                Method::GET => {
-                    let Ok(file) = tokio::fs::File::open(file_path).await else {
-                        break 'files not_found();
-                    };
-
-                    builder
-                        .header(CONTENT_TYPE, HeaderValue::from_static(OCTET_STREAM))
-                        .header(CONTENT_LENGTH, file.metadata().await.map(|md| md.len())?)
-                        .body(BodyType::chunked(file))?
-                }
-                Method::POST => {
-                    let Some(body) = request.body else {
-                        bail!("No body");
-                    };
-                    tokio::fs::create_dir_all(&directory).await?;
-                    let mut file = tokio::fs::OpenOptions::new()
-                        .write(true)
-                        .truncate(true)
-                        .create(true)
-                        .open(file_path)
-                        .await?;
-
-                    write_body_to(&mut file, BodyType::Chunked(body)).await?;
-                    builder.status(StatusCode::CREATED).body(BodyType::Empty)?
-                }
-                _ => unimplemented!(),
-            }
-        }
-        _ => not_found(),
+            .get_all(CONNECTION)
+            .into_iter()
+            .flat_map(|hv| hv.as_bytes().split_str(b","))
+            .any(|part| part.trim().eq_ignore_ascii_case(token.as_bytes()))
     };
-    send_response(response, responder).await?;
-    Ok(())
+
+    if has_token("close") {
+        false
+    } else if has_token("keep-alive") {
+        true
+    } else {
+        *request.version() >= Version::HTTP_11
+    }
 }
 
-async fn send_response<W>(response: Response<BodyType>, writer: &mut W) -> anyhow::Result<()>
+async fn send_response<W>(
+    response: Response<BodyType>,
+    keep_alive: bool,
+    writer: &mut W,
+) -> anyhow::Result<()>
 where
     W: AsyncWriteExt + Unpin,
 {
-    let (parts, body) = response.into_parts();
+    let (mut parts, body) = response.into_parts();
+
+    // Every non-chunked response must declare its length up front so a keep-alive client knows
+    // where it ends without relying on the connection closing; callers that stream a body of
+    // known size (e.g. serving a file) already set this themselves. A `101` carries no body and
+    // no `Content-Length` per RFC 7230 section 3.3.2.
+    if !parts.headers.contains_key(CONTENT_LENGTH) && parts.status != StatusCode::SWITCHING_PROTOCOLS
+    {
+        let len = match &body {
+            BodyType::Full(bytes) => Some(bytes.len()),
+            BodyType::Empty => Some(0),
+            BodyType::Chunked(_) => None,
+        };
+        if let Some(len) = len {
+            parts.headers.insert(CONTENT_LENGTH, len.into());
+        }
+    }
 
     writer.write_all(b"HTTP/").await?;
     match parts.version {
@@ -142,6 +318,16 @@ where
         writer.write_all(value.as_ref()).await?;
         writer.write_all(b"\r\n").await?;
     }
+
+    // A handshake response already states its own `Connection: Upgrade`; don't also assert
+    // keep-alive/close over it.
+    if !parts.headers.contains_key(CONNECTION) {
+        writer.write_all(b"Connection: ").await?;
+        writer
+            .write_all(if keep_alive { b"keep-alive" } else { b"close" })
+            .await?;
+        writer.write_all(b"\r\n").await?;
+    }
     writer.write_all(b"\r\n").await?;
 
     write_body_to(writer, body).await?;
@@ -170,7 +356,7 @@ where
     Ok(())
 }
 
-enum BodyType {
+pub(crate) enum BodyType {
     Full(Bytes),
     Chunked(BoxBody),
     Empty,
@@ -191,13 +377,84 @@ impl BodyType {
     }
 }
 
-fn not_found() -> Response<BodyType> {
+pub(crate) fn not_found() -> Response<BodyType> {
     Response::builder()
         .status(StatusCode::NOT_FOUND)
         .body(BodyType::full("Not Found"))
         .unwrap()
 }
 
+/// A path matched a registered route but not for this method; lists the methods that are
+/// registered for it in the `Allow` header, per RFC 7231 section 6.5.5.
+pub(crate) fn method_not_allowed(methods: &[Method]) -> Response<BodyType> {
+    let allow = methods
+        .iter()
+        .map(Method::as_str)
+        .collect::<Vec<_>>()
+        .join(", ");
+
+    Response::builder()
+        .status(StatusCode::METHOD_NOT_ALLOWED)
+        .header(ALLOW, allow)
+        .body(BodyType::Empty)
+        .unwrap()
+}
+
+fn accepts_gzip(headers: &HeaderMap) -> bool {
+    headers
+        .get_all(ACCEPT_ENCODING)
+        .into_iter()
+        .flat_map(|hv| hv.as_bytes().split_str(b", "))
+        .any(|hv| hv.eq_ignore_ascii_case(b"gzip"))
+}
+
+/// Path of the precompressed sidecar for `path`, e.g. `foo.txt` -> `foo.txt.gz`.
+fn gz_sidecar_path(path: &Path) -> PathBuf {
+    let mut name = path.as_os_str().to_owned();
+    name.push(".gz");
+    PathBuf::from(name)
+}
+
+/// The `Content-Type` to serve `file_name` with: `?download=1` in `query` always forces
+/// `application/octet-stream` (for forcing a download rather than inline rendering), otherwise
+/// it's looked up from the file's extension via [`mime_type`].
+fn content_type_for(file_name: &str, query: Option<&str>) -> &'static str {
+    let wants_download = query
+        .into_iter()
+        .flat_map(|q| q.split('&'))
+        .any(|pair| pair == "download=1");
+
+    if wants_download {
+        OCTET_STREAM
+    } else {
+        mime_type(file_name)
+    }
+}
+
+/// Looks up a MIME type from a file name's extension, reusable by any handler that serves file
+/// contents. Falls back to `application/octet-stream` for unknown or missing extensions.
+fn mime_type(file_name: &str) -> &'static str {
+    let extension = Path::new(file_name)
+        .extension()
+        .and_then(|ext| ext.to_str())
+        .unwrap_or_default();
+
+    match extension.to_ascii_lowercase().as_str() {
+        "html" | "htm" => "text/html",
+        "txt" => TEXT_PLAIN,
+        "css" => "text/css",
+        "js" => "text/javascript",
+        "json" => "application/json",
+        "xml" => "application/xml",
+        "pdf" => "application/pdf",
+        "png" => "image/png",
+        "jpg" | "jpeg" => "image/jpeg",
+        "gif" => "image/gif",
+        "svg" => "image/svg+xml",
+        _ => OCTET_STREAM,
+    }
+}
+
 fn encode_sync(bytes: impl AsRef<[u8]>) -> Result<Vec<u8>, std::io::Error> {
     let bytes = bytes.as_ref();
     let buf = Vec::new();
@@ -205,3 +462,164 @@ fn encode_sync(bytes: impl AsRef<[u8]>) -> Result<Vec<u8>, std::io::Error> {
     encoder.write_all(bytes)?;
     encoder.finish()
 }
+
+fn decode_sync(bytes: impl AsRef<[u8]>) -> Result<Vec<u8>, std::io::Error> {
+    let mut decoder = GzDecoder::new(bytes.as_ref());
+    let mut buf = Vec::new();
+    decoder.read_to_end(&mut buf)?;
+    Ok(buf)
+}
+
+#[derive(Debug, PartialEq, Eq)]
+enum RangeOutcome {
+    Full,
+    Partial(Range<u64>),
+}
+
+/// Parses a `Range: bytes=...` header against a resource of `total` bytes, supporting a single
+/// `start-end`, `start-`, or `-suffix_len` range. Returns `None` when the range is present but
+/// unsatisfiable, and also for a `bytes=` spec that doesn't parse as one such range (including
+/// multi-range lists); a missing header, or one whose unit isn't `bytes`, is treated as a request
+/// for the whole resource.
+fn resolve_range(headers: &HeaderMap, total: u64) -> Option<RangeOutcome> {
+    let Some(value) = headers.get(RANGE).and_then(|v| v.to_str().ok()) else {
+        return Some(RangeOutcome::Full);
+    };
+    let Some(spec) = value.strip_prefix("bytes=") else {
+        return Some(RangeOutcome::Full);
+    };
+    let (start, end) = spec.split_once('-')?;
+
+    let (start, end) = match (start.is_empty(), end.is_empty()) {
+        (true, true) => return None,
+        (true, false) => {
+            let suffix_len: u64 = end.parse().ok()?;
+            if suffix_len == 0 {
+                return None;
+            }
+            (total.saturating_sub(suffix_len), total.checked_sub(1)?)
+        }
+        (false, true) => (start.parse().ok()?, total.checked_sub(1)?),
+        (false, false) => {
+            let start: u64 = start.parse().ok()?;
+            let end: u64 = end.parse().ok()?;
+            (start, end.min(total.saturating_sub(1)))
+        }
+    };
+
+    (start < total && start <= end).then_some(RangeOutcome::Partial(start..end + 1))
+}
+
+fn range_not_satisfiable(total: u64) -> Response<BodyType> {
+    Response::builder()
+        .status(StatusCode::RANGE_NOT_SATISFIABLE)
+        .header(CONTENT_RANGE, format!("bytes */{total}"))
+        .body(BodyType::Empty)
+        .unwrap()
+}
+
+/// Applies `Accept-Ranges`/`Content-Length` (and `Content-Range`/206 status for a partial range)
+/// to `builder`, returning it alongside the `(start, len)` byte window the caller should serve.
+fn apply_range_headers(
+    builder: response::Builder,
+    range: RangeOutcome,
+    total: u64,
+) -> (response::Builder, u64, u64) {
+    let builder = builder.header(ACCEPT_RANGES, HeaderValue::from_static("bytes"));
+    match range {
+        RangeOutcome::Full => (builder.header(CONTENT_LENGTH, total), 0, total),
+        RangeOutcome::Partial(range) => {
+            let len = range.end - range.start;
+            let builder = builder
+                .status(StatusCode::PARTIAL_CONTENT)
+                .header(
+                    CONTENT_RANGE,
+                    format!("bytes {}-{}/{total}", range.start, range.end - 1),
+                )
+                .header(CONTENT_LENGTH, len);
+            (builder, range.start, len)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn headers_with_range(range: &str) -> HeaderMap {
+        let mut headers = HeaderMap::new();
+        headers.insert(RANGE, HeaderValue::from_str(range).unwrap());
+        headers
+    }
+
+    #[test]
+    fn missing_header_is_the_full_resource() {
+        assert_eq!(
+            resolve_range(&HeaderMap::new(), 100),
+            Some(RangeOutcome::Full)
+        );
+    }
+
+    #[test]
+    fn closed_range_is_inclusive() {
+        assert_eq!(
+            resolve_range(&headers_with_range("bytes=0-99"), 200),
+            Some(RangeOutcome::Partial(0..100))
+        );
+    }
+
+    #[test]
+    fn open_ended_range_runs_to_the_last_byte() {
+        assert_eq!(
+            resolve_range(&headers_with_range("bytes=100-"), 150),
+            Some(RangeOutcome::Partial(100..150))
+        );
+    }
+
+    #[test]
+    fn suffix_range_takes_the_last_n_bytes() {
+        assert_eq!(
+            resolve_range(&headers_with_range("bytes=-10"), 100),
+            Some(RangeOutcome::Partial(90..100))
+        );
+    }
+
+    #[test]
+    fn suffix_longer_than_the_resource_clamps_to_its_start() {
+        assert_eq!(
+            resolve_range(&headers_with_range("bytes=-1000"), 100),
+            Some(RangeOutcome::Partial(0..100))
+        );
+    }
+
+    #[test]
+    fn end_past_the_resource_clamps_to_the_last_byte() {
+        assert_eq!(
+            resolve_range(&headers_with_range("bytes=50-1000"), 100),
+            Some(RangeOutcome::Partial(50..100))
+        );
+    }
+
+    #[test]
+    fn start_at_or_past_total_is_unsatisfiable() {
+        assert_eq!(resolve_range(&headers_with_range("bytes=100-"), 100), None);
+    }
+
+    #[test]
+    fn zero_length_suffix_is_unsatisfiable() {
+        assert_eq!(resolve_range(&headers_with_range("bytes=-0"), 100), None);
+    }
+
+    #[test]
+    fn missing_start_and_end_is_unsatisfiable() {
+        assert_eq!(resolve_range(&headers_with_range("bytes=-"), 100), None);
+    }
+
+    #[test]
+    fn unrecognized_unit_is_treated_as_the_full_resource() {
+        assert_eq!(
+            resolve_range(&headers_with_range("items=0-1"), 100),
+            Some(RangeOutcome::Full)
+        );
+    }
+}
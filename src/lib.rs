@@ -0,0 +1,9 @@
+pub mod args;
+pub mod handler;
+pub mod request;
+pub mod router;
+pub mod ws;
+
+pub use args::ARGUMENTS;
+pub use handler::{handle_request, Outcome};
+pub use request::{Request, RequestParser};
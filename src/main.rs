@@ -1,10 +1,17 @@
-use http_server_starter_rust::{handle_request, RequestParser, ARGUMENTS};
+use std::time::Duration;
+
+use http_server_starter_rust::{handle_request, ws, Outcome, RequestParser, ARGUMENTS};
 use once_cell::sync::Lazy;
 use tokio::{
-    io::{BufReader, BufWriter},
+    io::{AsyncBufReadExt, BufReader, BufWriter},
     net::{TcpListener, TcpStream},
+    time::timeout,
 };
 
+/// How long to wait for the next request on a keep-alive connection before dropping it, so an
+/// idle client doesn't leak its spawned task forever.
+const IDLE_TIMEOUT: Duration = Duration::from_secs(75);
+
 #[tokio::main]
 async fn main() -> anyhow::Result<()> {
     Lazy::force(&ARGUMENTS);
@@ -27,15 +34,38 @@ async fn main() -> anyhow::Result<()> {
 }
 
 async fn handle_connection(stream: TcpStream) -> anyhow::Result<()> {
-    let (reader, mut writer) = {
+    let (mut reader, mut writer) = {
         let (reader, writer) = stream.into_split();
         let reader = BufReader::new(reader);
         let writer = BufWriter::new(writer);
         (reader, writer)
     };
 
-    let request = RequestParser::parse(reader).await?;
+    loop {
+        // Only the wait for the *next* request to start is bounded by `IDLE_TIMEOUT`; once bytes
+        // have arrived, `parse` (header lines, then a content-length or chunked body) is allowed
+        // to take as long as it needs, so a slow-but-active upload isn't mistaken for an idle
+        // connection and cut off mid-transfer.
+        match timeout(IDLE_TIMEOUT, reader.fill_buf()).await {
+            Ok(Ok([])) => break,
+            Ok(Ok(_)) => {}
+            Ok(Err(err)) => return Err(err.into()),
+            Err(_) => break,
+        }
+
+        let Some(request) = RequestParser::parse(&mut reader).await? else {
+            break;
+        };
+
+        match handle_request(request, &mut writer).await? {
+            Outcome::Response(true) => {}
+            Outcome::Response(false) => break,
+            // The `101` is already on the wire; drop the `BufWriter` so the frame loop writes
+            // straight to the socket instead of through its buffer. `ws::serve` applies
+            // `IDLE_TIMEOUT` to each frame read itself, same as the request loop above.
+            Outcome::Upgraded => return ws::serve(reader, writer.into_inner(), IDLE_TIMEOUT).await,
+        }
+    }
 
-    handle_request(request, &mut writer).await?;
     Ok(())
 }
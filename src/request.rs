@@ -1,7 +1,11 @@
-use anyhow::{bail, ensure};
+use anyhow::{bail, ensure, Context};
+use bstr::ByteSlice;
 use bytes::Bytes;
-use futures_util::TryStreamExt;
-use http::{header::CONTENT_LENGTH, HeaderMap, HeaderName, HeaderValue, Method, Uri, Version};
+use futures_util::stream;
+use http::{
+    header::{CONTENT_LENGTH, TRANSFER_ENCODING},
+    HeaderMap, HeaderName, HeaderValue, Method, Uri, Version,
+};
 use http_body::Frame;
 use http_body_util::{combinators::BoxBody as _BB, BodyExt, StreamBody};
 use nom::{
@@ -14,21 +18,27 @@ use nom::{
     IResult, Parser,
 };
 use tokio::io::{AsyncBufReadExt, AsyncReadExt};
-use tokio_util::io::ReaderStream;
 
-type BoxBody = _BB<Bytes, std::io::Error>;
+pub(crate) type BoxBody = _BB<Bytes, std::io::Error>;
 pub struct RequestParser;
 
 impl RequestParser {
-    pub async fn parse<R>(mut reader: R) -> anyhow::Result<Request<BoxBody>>
+    /// Parses a single request off `reader`, borrowing it rather than consuming it so the
+    /// caller can keep reading further pipelined/keep-alive requests off the same stream.
+    /// Returns `Ok(None)` when the peer closed the connection before sending anything, which
+    /// callers should treat as a clean end of the connection rather than an error.
+    pub async fn parse<R>(reader: &mut R) -> anyhow::Result<Option<Request<BoxBody>>>
     where
-        R: AsyncBufReadExt + Unpin + Send + Sync + 'static,
+        R: AsyncBufReadExt + Unpin + Send + Sync,
     {
         let parts = {
             let mut buf = Vec::with_capacity(512);
 
             loop {
                 if 0 == reader.read_until(b'\n', &mut buf).await? {
+                    if buf.is_empty() {
+                        return Ok(None);
+                    }
                     bail!("Incomplete request");
                 }
                 if buf.ends_with(b"\r\n\r\n") {
@@ -38,20 +48,88 @@ impl RequestParser {
             Parts::parse(buf)?
         };
 
-        let body = {
+        // `Transfer-Encoding` can be a comma-separated list (e.g. `gzip, chunked`); per RFC 7230
+        // section 3.3.1, `chunked` must be the last one applied, so only the final token matters.
+        let is_chunked = parts
+            .headers
+            .get(TRANSFER_ENCODING)
+            .and_then(|x| x.to_str().ok())
+            .and_then(|x| x.split(',').next_back())
+            .is_some_and(|x| x.trim().eq_ignore_ascii_case("chunked"));
+
+        let body = if is_chunked {
+            let frames = Self::read_chunked_body(reader).await?;
+            let stream = stream::iter(frames.into_iter().map(Ok::<_, std::io::Error>));
+            Some(StreamBody::new(stream).boxed())
+        } else {
             let content_length = parts
                 .headers
                 .get(CONTENT_LENGTH)
                 .and_then(|x| x.to_str().ok())
                 .and_then(|x| x.parse::<u64>().ok());
 
-            content_length.filter(|x| *x != 0).map(|len| {
-                let reader = reader.take(len);
-                let stream = ReaderStream::new(reader);
-                StreamBody::new(stream.map_ok(Frame::data)).boxed()
-            })
+            match content_length.filter(|x| *x != 0) {
+                Some(len) => {
+                    let mut buf = vec![0; len as usize];
+                    reader.read_exact(&mut buf).await?;
+                    let frame = Frame::data(Bytes::from(buf));
+                    let stream = stream::once(async move { Ok::<_, std::io::Error>(frame) });
+                    Some(StreamBody::new(stream).boxed())
+                }
+                None => None,
+            }
         };
-        Ok(Request { parts, body })
+        Ok(Some(Request { parts, body }))
+    }
+
+    /// Decodes an RFC 7230 chunked body: repeatedly reads a `<hex-size>[;ext]\r\n` line followed
+    /// by exactly that many data bytes and a trailing CRLF, stopping at the `0` size chunk, then
+    /// consumes its (ignored) trailer headers and the final CRLF. Leaves `reader` positioned at
+    /// the start of whatever follows, e.g. the next pipelined request.
+    async fn read_chunked_body<R>(reader: &mut R) -> anyhow::Result<Vec<Frame<Bytes>>>
+    where
+        R: AsyncBufReadExt + Unpin,
+    {
+        let mut frames = Vec::new();
+
+        loop {
+            let mut size_line = Vec::new();
+            reader.read_until(b'\n', &mut size_line).await?;
+            ensure!(size_line.ends_with(b"\r\n"), "chunk size line missing CRLF");
+            let size_line = &size_line[..size_line.len() - 2];
+
+            let hex_size = size_line
+                .split(|&b| b == b';')
+                .next()
+                .unwrap_or(size_line)
+                .trim_ascii();
+            let size = std::str::from_utf8(hex_size)
+                .ok()
+                .and_then(|s| u64::from_str_radix(s, 16).ok())
+                .with_context(|| format!("invalid chunk size: {:?}", hex_size.as_bstr()))?;
+
+            if size == 0 {
+                loop {
+                    let mut trailer_line = Vec::new();
+                    reader.read_until(b'\n', &mut trailer_line).await?;
+                    if trailer_line.is_empty() || trailer_line == b"\r\n" {
+                        break;
+                    }
+                }
+                break;
+            }
+
+            let mut data = vec![0; size as usize];
+            reader.read_exact(&mut data).await?;
+
+            let mut crlf = [0; 2];
+            reader.read_exact(&mut crlf).await?;
+            ensure!(&crlf == b"\r\n", "chunk data missing trailing CRLF");
+
+            frames.push(Frame::data(Bytes::from(data)));
+        }
+
+        Ok(frames)
     }
 }
 
@@ -159,3 +237,81 @@ impl Parts {
         terminated(headers, crlf).parse(input)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use std::io::Cursor;
+
+    use tokio::io::BufReader;
+
+    use super::*;
+
+    async fn read_chunked_body(input: &[u8]) -> anyhow::Result<Vec<u8>> {
+        let mut reader = BufReader::new(Cursor::new(input));
+        let frames = RequestParser::read_chunked_body(&mut reader).await?;
+        Ok(frames
+            .into_iter()
+            .flat_map(|frame| frame.into_data().unwrap())
+            .collect())
+    }
+
+    #[tokio::test]
+    async fn decodes_multiple_chunks_to_zero_size_terminator() {
+        let body = read_chunked_body(b"4\r\nWiki\r\n5\r\npedia\r\n0\r\n\r\n")
+            .await
+            .unwrap();
+        assert_eq!(body, b"Wikipedia");
+    }
+
+    #[tokio::test]
+    async fn zero_length_body_has_only_the_terminator() {
+        let body = read_chunked_body(b"0\r\n\r\n").await.unwrap();
+        assert!(body.is_empty());
+    }
+
+    #[tokio::test]
+    async fn ignores_chunk_extensions() {
+        let body = read_chunked_body(b"4;ext=1\r\nWiki\r\n0\r\n\r\n")
+            .await
+            .unwrap();
+        assert_eq!(body, b"Wiki");
+    }
+
+    #[tokio::test]
+    async fn consumes_trailer_headers_after_the_terminator() {
+        let body = read_chunked_body(b"0\r\nX-Trailer: value\r\n\r\n")
+            .await
+            .unwrap();
+        assert!(body.is_empty());
+    }
+
+    #[tokio::test]
+    async fn rejects_malformed_hex_size() {
+        assert!(read_chunked_body(b"zz\r\nWiki\r\n0\r\n\r\n").await.is_err());
+    }
+
+    #[tokio::test]
+    async fn rejects_chunk_data_missing_trailing_crlf() {
+        assert!(read_chunked_body(b"4\r\nWikiXX").await.is_err());
+    }
+
+    #[tokio::test]
+    async fn recognizes_chunked_as_the_last_of_a_transfer_encoding_list() {
+        let mut reader = BufReader::new(Cursor::new(
+            &b"POST /upload HTTP/1.1\r\n\
+               Transfer-Encoding: gzip, chunked\r\n\
+               \r\n\
+               4\r\nWiki\r\n0\r\n\r\n"[..],
+        ));
+
+        let request = RequestParser::parse(&mut reader).await.unwrap().unwrap();
+        let body = request
+            .body
+            .unwrap()
+            .collect()
+            .await
+            .unwrap()
+            .to_bytes();
+        assert_eq!(body.as_ref(), b"Wiki");
+    }
+}
@@ -0,0 +1,119 @@
+use std::{collections::HashMap, future::Future, pin::Pin, sync::Arc};
+
+use anyhow::Result;
+use http::{Method, Response};
+
+use crate::{
+    handler::{method_not_allowed, not_found, BodyType},
+    request::BoxBody,
+    Request,
+};
+
+/// Named path segments captured by a route pattern, e.g. `:msg` in `/echo/:msg`.
+pub type Params = HashMap<String, String>;
+
+type HandlerFuture = Pin<Box<dyn Future<Output = Result<Response<BodyType>>> + Send>>;
+type HandlerFn = Arc<dyn Fn(Request<BoxBody>, Params) -> HandlerFuture + Send + Sync>;
+
+enum Segment {
+    Literal(String),
+    Param(String),
+    Wildcard(String),
+}
+
+struct Route {
+    method: Method,
+    segments: Vec<Segment>,
+    handler: HandlerFn,
+}
+
+/// Dispatches requests to handlers registered by `(Method, pattern)`, where `pattern` may use
+/// `:name` for a single named segment or a trailing `*name` to capture the rest of the path.
+#[derive(Default)]
+pub(crate) struct Router {
+    routes: Vec<Route>,
+}
+
+impl Router {
+    pub(crate) fn new() -> Self {
+        Self::default()
+    }
+
+    pub(crate) fn route<F, Fut>(&mut self, method: Method, pattern: &str, handler: F) -> &mut Self
+    where
+        F: Fn(Request<BoxBody>, Params) -> Fut + Send + Sync + 'static,
+        Fut: Future<Output = Result<Response<BodyType>>> + Send + 'static,
+    {
+        self.routes.push(Route {
+            method,
+            segments: parse_pattern(pattern),
+            handler: Arc::new(move |request, params| Box::pin(handler(request, params))),
+        });
+        self
+    }
+
+    /// Matches `request`'s method and path against the registered routes. Paths that match but
+    /// whose method isn't registered get a `405` listing the methods that are; paths that match
+    /// nothing get a `404`.
+    pub(crate) async fn dispatch(&self, request: Request<BoxBody>) -> Result<Response<BodyType>> {
+        let path = request.uri().path().to_owned();
+
+        let mut allowed_methods = Vec::new();
+        for route in &self.routes {
+            let Some(params) = match_path(&route.segments, &path) else {
+                continue;
+            };
+            if route.method == *request.method() {
+                return (route.handler)(request, params).await;
+            }
+            allowed_methods.push(route.method.clone());
+        }
+
+        Ok(if allowed_methods.is_empty() {
+            not_found()
+        } else {
+            method_not_allowed(&allowed_methods)
+        })
+    }
+}
+
+fn parse_pattern(pattern: &str) -> Vec<Segment> {
+    pattern
+        .split('/')
+        .filter(|segment| !segment.is_empty())
+        .map(|segment| {
+            if let Some(name) = segment.strip_prefix(':') {
+                Segment::Param(name.to_owned())
+            } else if let Some(name) = segment.strip_prefix('*') {
+                Segment::Wildcard(name.to_owned())
+            } else {
+                Segment::Literal(segment.to_owned())
+            }
+        })
+        .collect()
+}
+
+fn match_path(segments: &[Segment], path: &str) -> Option<Params> {
+    let mut params = Params::new();
+    let mut parts = path.split('/').filter(|part| !part.is_empty());
+
+    for (i, segment) in segments.iter().enumerate() {
+        match segment {
+            Segment::Literal(literal) => {
+                if parts.next()? != literal {
+                    return None;
+                }
+            }
+            Segment::Param(name) => {
+                params.insert(name.clone(), parts.next()?.to_owned());
+            }
+            Segment::Wildcard(name) => {
+                let rest = parts.by_ref().collect::<Vec<_>>().join("/");
+                params.insert(name.clone(), rest);
+                return (i == segments.len() - 1).then_some(params);
+            }
+        }
+    }
+
+    parts.next().is_none().then_some(params)
+}
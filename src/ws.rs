@@ -0,0 +1,285 @@
+use std::time::Duration;
+
+use anyhow::{bail, ensure};
+use base64::{engine::general_purpose::STANDARD, Engine};
+use sha1::{Digest, Sha1};
+use tokio::{
+    io::{AsyncRead, AsyncReadExt, AsyncWrite, AsyncWriteExt},
+    time::timeout,
+};
+
+/// Fixed per RFC 6455 section 1.3, appended to the client's `Sec-WebSocket-Key` before hashing.
+const GUID: &str = "258EAFA5-E914-47DA-95CA-C5AB0DC85B11";
+
+/// Computes the `Sec-WebSocket-Accept` value for a handshake: SHA-1 of the client's
+/// `Sec-WebSocket-Key` concatenated with the fixed [`GUID`], base64-encoded.
+pub(crate) fn accept_key(client_key: &str) -> String {
+    let mut hasher = Sha1::new();
+    hasher.update(client_key.as_bytes());
+    hasher.update(GUID.as_bytes());
+    STANDARD.encode(hasher.finalize())
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Opcode {
+    Continuation,
+    Text,
+    Binary,
+    Close,
+    Ping,
+    Pong,
+}
+
+impl Opcode {
+    fn from_byte(byte: u8) -> anyhow::Result<Self> {
+        Ok(match byte {
+            0x0 => Self::Continuation,
+            0x1 => Self::Text,
+            0x2 => Self::Binary,
+            0x8 => Self::Close,
+            0x9 => Self::Ping,
+            0xA => Self::Pong,
+            other => bail!("unsupported websocket opcode: {other:#x}"),
+        })
+    }
+
+    fn as_byte(self) -> u8 {
+        match self {
+            Self::Continuation => 0x0,
+            Self::Text => 0x1,
+            Self::Binary => 0x2,
+            Self::Close => 0x8,
+            Self::Ping => 0x9,
+            Self::Pong => 0xA,
+        }
+    }
+}
+
+#[derive(Debug, PartialEq, Eq)]
+struct Frame {
+    opcode: Opcode,
+    payload: Vec<u8>,
+}
+
+impl Frame {
+    /// Reads a single RFC 6455 frame off `reader`: the FIN/opcode byte, a masked 7/16/64-bit
+    /// payload length, the 4-byte masking key, then the payload itself, unmasked in place.
+    /// Fragmented messages (FIN unset) aren't supported since neither test client sends them.
+    async fn read<R>(reader: &mut R) -> anyhow::Result<Self>
+    where
+        R: AsyncRead + Unpin,
+    {
+        let mut head = [0u8; 2];
+        reader.read_exact(&mut head).await?;
+        ensure!(head[0] & 0x80 != 0, "fragmented frames are not supported");
+        let opcode = Opcode::from_byte(head[0] & 0x0F)?;
+        ensure!(head[1] & 0x80 != 0, "client frames must be masked");
+
+        let len = match head[1] & 0x7F {
+            126 => reader.read_u16().await? as u64,
+            127 => reader.read_u64().await?,
+            len => len as u64,
+        };
+
+        let mut mask = [0u8; 4];
+        reader.read_exact(&mut mask).await?;
+
+        let mut payload = vec![0u8; len as usize];
+        reader.read_exact(&mut payload).await?;
+        for (i, byte) in payload.iter_mut().enumerate() {
+            *byte ^= mask[i % 4];
+        }
+
+        Ok(Self { opcode, payload })
+    }
+
+    /// Writes `self` as a single, unmasked, FIN-set server frame, per RFC 6455 section 5.2;
+    /// servers never mask their frames.
+    async fn write<W>(&self, writer: &mut W) -> anyhow::Result<()>
+    where
+        W: AsyncWrite + Unpin,
+    {
+        writer.write_u8(0x80 | self.opcode.as_byte()).await?;
+
+        let len = self.payload.len();
+        if len < 126 {
+            writer.write_u8(len as u8).await?;
+        } else if len <= u16::MAX as usize {
+            writer.write_u8(126).await?;
+            writer.write_u16(len as u16).await?;
+        } else {
+            writer.write_u8(127).await?;
+            writer.write_u64(len as u64).await?;
+        }
+
+        writer.write_all(&self.payload).await?;
+        writer.flush().await?;
+        Ok(())
+    }
+}
+
+/// Runs the post-handshake frame loop on a connection that has upgraded to WebSocket: echoes
+/// text/binary frames back, answers pings with pongs, ignores pongs, and returns once a close
+/// frame has been exchanged in both directions. Each frame read is bounded by `idle_timeout`, so
+/// a peer that never sends another frame doesn't leak the spawned task forever, same as the
+/// `IDLE_TIMEOUT` applied to the HTTP request loop.
+pub async fn serve<R, W>(mut reader: R, mut writer: W, idle_timeout: Duration) -> anyhow::Result<()>
+where
+    R: AsyncRead + Unpin,
+    W: AsyncWrite + Unpin,
+{
+    loop {
+        let frame = match timeout(idle_timeout, Frame::read(&mut reader)).await {
+            Ok(frame) => frame?,
+            Err(_) => return Ok(()),
+        };
+        match frame.opcode {
+            Opcode::Text | Opcode::Binary => {
+                Frame {
+                    opcode: frame.opcode,
+                    payload: frame.payload,
+                }
+                .write(&mut writer)
+                .await?;
+            }
+            Opcode::Ping => {
+                Frame {
+                    opcode: Opcode::Pong,
+                    payload: frame.payload,
+                }
+                .write(&mut writer)
+                .await?;
+            }
+            Opcode::Pong => {}
+            Opcode::Close => {
+                Frame {
+                    opcode: Opcode::Close,
+                    payload: frame.payload,
+                }
+                .write(&mut writer)
+                .await?;
+                return Ok(());
+            }
+            Opcode::Continuation => bail!("fragmented frames are not supported"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::Cursor;
+
+    use super::*;
+
+    /// Masks `payload` with `mask` the way a client frame does, so tests can build wire bytes
+    /// without hand-XORing.
+    fn masked(mask: [u8; 4], payload: &[u8]) -> Vec<u8> {
+        payload
+            .iter()
+            .enumerate()
+            .map(|(i, byte)| byte ^ mask[i % 4])
+            .collect()
+    }
+
+    #[test]
+    fn accept_key_matches_the_rfc_6455_worked_example() {
+        assert_eq!(
+            accept_key("dGhlIHNhbXBsZSBub25jZQ=="),
+            "s3pPLMBiTxaQ9kYGzzhZRbK+xOo="
+        );
+    }
+
+    #[tokio::test]
+    async fn reads_a_small_masked_text_frame() {
+        let mask = [0x12, 0x34, 0x56, 0x78];
+        let mut wire = vec![0x81, 0x80 | 5];
+        wire.extend(mask);
+        wire.extend(masked(mask, b"Hello"));
+
+        let frame = Frame::read(&mut Cursor::new(wire)).await.unwrap();
+        assert_eq!(
+            frame,
+            Frame {
+                opcode: Opcode::Text,
+                payload: b"Hello".to_vec(),
+            }
+        );
+    }
+
+    #[tokio::test]
+    async fn reads_a_16_bit_extended_length_frame() {
+        let mask = [0xAA, 0xBB, 0xCC, 0xDD];
+        let payload = vec![b'x'; 300];
+        let mut wire = vec![0x82, 0x80 | 126];
+        wire.extend(300u16.to_be_bytes());
+        wire.extend(mask);
+        wire.extend(masked(mask, &payload));
+
+        let frame = Frame::read(&mut Cursor::new(wire)).await.unwrap();
+        assert_eq!(
+            frame,
+            Frame {
+                opcode: Opcode::Binary,
+                payload,
+            }
+        );
+    }
+
+    #[tokio::test]
+    async fn reads_a_64_bit_extended_length_frame() {
+        let mask = [0x01, 0x02, 0x03, 0x04];
+        let payload = vec![b'y'; 70_000];
+        let mut wire = vec![0x82, 0x80 | 127];
+        wire.extend((70_000u64).to_be_bytes());
+        wire.extend(mask);
+        wire.extend(masked(mask, &payload));
+
+        let frame = Frame::read(&mut Cursor::new(wire)).await.unwrap();
+        assert_eq!(
+            frame,
+            Frame {
+                opcode: Opcode::Binary,
+                payload,
+            }
+        );
+    }
+
+    #[tokio::test]
+    async fn rejects_an_unmasked_frame() {
+        let wire = vec![0x81, 5, b'H', b'e', b'l', b'l', b'o'];
+        assert!(Frame::read(&mut Cursor::new(wire)).await.is_err());
+    }
+
+    #[tokio::test]
+    async fn rejects_a_fragmented_frame() {
+        let mask = [0; 4];
+        let mut wire = vec![0x01, 0x80];
+        wire.extend(mask);
+        assert!(Frame::read(&mut Cursor::new(wire)).await.is_err());
+    }
+
+    #[tokio::test]
+    async fn writes_an_unmasked_server_frame_with_a_small_length() {
+        let frame = Frame {
+            opcode: Opcode::Pong,
+            payload: b"pong".to_vec(),
+        };
+        let mut out = Vec::new();
+        frame.write(&mut out).await.unwrap();
+        assert_eq!(out, [0x8A, 4, b'p', b'o', b'n', b'g']);
+    }
+
+    #[tokio::test]
+    async fn writes_a_16_bit_extended_length() {
+        let frame = Frame {
+            opcode: Opcode::Binary,
+            payload: vec![0; 200],
+        };
+        let mut out = Vec::new();
+        frame.write(&mut out).await.unwrap();
+        assert_eq!(out[0], 0x82);
+        assert_eq!(out[1], 126);
+        assert_eq!(&out[2..4], &200u16.to_be_bytes());
+        assert_eq!(out.len(), 4 + 200);
+    }
+}